@@ -1,25 +1,30 @@
 use anyhow::Result;
+use chrono::{DateTime, Local, Utc};
 use clap::{Parser, Subcommand};
 use comfy_table::{presets::UTF8_FULL, CellAlignment, ColumnConstraint, Row, Table, Width::Fixed};
 use crossterm::{
-    cursor::{Hide, MoveDown, MoveUp, Show},
+    cursor::{Hide, MoveDown, MoveTo, MoveUp, Show},
+    event::{self, Event, KeyCode, KeyEvent, KeyEventKind},
     execute,
     style::Stylize,
-    terminal::{Clear, ClearType},
+    terminal::{
+        self, disable_raw_mode, enable_raw_mode, Clear, ClearType, EnterAlternateScreen,
+        LeaveAlternateScreen,
+    },
 };
 use rand::{
     seq::{IteratorRandom, SliceRandom},
     thread_rng, Rng,
 };
 use std::{
-    collections::BTreeSet,
+    collections::{BTreeMap, BTreeSet},
     env,
     fs::{self, File, OpenOptions},
     io::{stdout, BufRead, BufReader, BufWriter, Write},
     path::PathBuf,
     process::Command,
     thread,
-    time::Duration,
+    time::{Duration, SystemTime, UNIX_EPOCH},
 };
 
 #[derive(Parser, Debug)]
@@ -30,40 +35,87 @@ struct Arguments {
     /// Operate on a list with the given name
     #[arg(short, long)]
     list: Option<String>,
+
+    /// Restrict to items carrying this tag
+    #[arg(short, long)]
+    tag: Option<String>,
 }
 
 #[derive(Subcommand, Debug)]
 enum Commands {
     /// Adds an item to the list
-    Add { name: String },
+    Add {
+        name: String,
+
+        /// How likely this marble is to win relative to the others
+        #[arg(short, long, default_value_t = 1)]
+        weight: u32,
+    },
 
     /// Removes an item from the list
     Remove { name: String },
 
+    /// Sets the weight of an existing item
+    Weight { name: String, value: u32 },
+
+    /// Adds tags to an existing item
+    Tag {
+        name: String,
+
+        /// Comma-separated tags to add
+        #[arg(value_delimiter = ',')]
+        tags: Vec<String>,
+    },
+
+    /// Removes tags from an item, or clears all of them if none are given
+    Untag {
+        name: String,
+
+        /// Comma-separated tags to remove
+        #[arg(value_delimiter = ',')]
+        tags: Vec<String>,
+    },
+
     /// Shows items in the list
     List,
 
+    /// Fuzzy-searches items in the list
+    Find { query: String },
+
     /// Rolls a random marble from the list, removing it
     Roll,
 
     /// Edits a list with $EDITOR
     Edit,
+
+    /// Opens an interactive full-screen browser over the list
+    Browse,
+
+    /// Restores the most recently discarded item
+    Undo,
+
+    /// Lists items that can be restored with `undo`
+    Trash,
+
+    /// Shows roll history and per-marble win stats
+    History,
 }
 
 fn main() -> Result<()> {
     let arguments = Arguments::parse();
     let list_name = arguments.list.unwrap_or_else(|| "default_list".to_string());
+    let tag_filter = arguments.tag;
 
     let mut list = ItemList::new(&list_name)?;
 
     match arguments.command {
-        Commands::Add { name } => {
+        Commands::Add { name, weight } => {
             println!(
                 "Added {} to {}",
                 name.as_str().underlined(),
                 list_name.bold().green()
             );
-            list.add(name);
+            list.add(name, weight);
         }
         Commands::Remove { name } => {
             println!(
@@ -71,7 +123,48 @@ fn main() -> Result<()> {
                 name.as_str().underlined(),
                 list_name.bold().green()
             );
-            if !list.remove(&name) {
+            if !list.discard(&name, "remove")? {
+                println!(
+                    "{} {} was not in list",
+                    "error:".bold().red(),
+                    name.underlined()
+                );
+            }
+        }
+        Commands::Weight { name, value } => {
+            if list.set_weight(&name, value) {
+                println!(
+                    "Set weight of {} to {}",
+                    name.as_str().underlined(),
+                    value.to_string().bold()
+                );
+            } else {
+                println!(
+                    "{} {} was not in list",
+                    "error:".bold().red(),
+                    name.underlined()
+                );
+            }
+        }
+        Commands::Tag { name, tags } => {
+            if list.add_tags(&name, &tags) {
+                println!(
+                    "Tagged {} with {}",
+                    name.as_str().underlined(),
+                    tags.join(", ").bold()
+                );
+            } else {
+                println!(
+                    "{} {} was not in list",
+                    "error:".bold().red(),
+                    name.underlined()
+                );
+            }
+        }
+        Commands::Untag { name, tags } => {
+            if list.remove_tags(&name, &tags) {
+                println!("Untagged {}", name.as_str().underlined());
+            } else {
                 println!(
                     "{} {} was not in list",
                     "error:".bold().red(),
@@ -80,24 +173,66 @@ fn main() -> Result<()> {
             }
         }
         Commands::List => {
+            let mut table = Table::new();
+            table
+                .load_preset(UTF8_FULL)
+                .set_header(vec!["#", "Title", "Weight", "Tags"]);
+            for (i, (name, item)) in list
+                .items
+                .iter()
+                .filter(|(_, item)| matches_tag(item, &tag_filter))
+                .enumerate()
+            {
+                table.add_row(vec![
+                    (i + 1).to_string(),
+                    name.to_string(),
+                    item.weight.to_string(),
+                    tags_to_string(&item.tags),
+                ]);
+            }
+
+            println!("{table}");
+        }
+        Commands::Find { query } => {
+            let mut matches = list
+                .items
+                .iter()
+                .filter(|(_, item)| matches_tag(item, &tag_filter))
+                .filter_map(|(name, _)| fuzzy_match(&query, name).map(|score| (score, name)))
+                .collect::<Vec<_>>();
+            matches.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+
             let mut table = Table::new();
             table.load_preset(UTF8_FULL).set_header(vec!["#", "Title"]);
-            for (i, item) in list.items.iter().enumerate() {
-                table.add_row(vec![(i + 1).to_string(), item.to_string()]);
+            for (i, (_, name)) in matches.iter().enumerate() {
+                table.add_row(vec![(i + 1).to_string(), name.to_string()]);
             }
 
             println!("{table}");
         }
         Commands::Roll => {
+            let mut choices = list
+                .items
+                .iter()
+                .filter(|(_, item)| matches_tag(item, &tag_filter))
+                .map(|(name, item)| (name.clone(), item.weight))
+                .collect::<Vec<(String, u32)>>();
+
+            if choices.is_empty() {
+                println!(
+                    "{} nothing matches that tag/filter",
+                    "error:".bold().red()
+                );
+                return Ok(());
+            }
+
             println!(
                 "Rolling a marble for {} of {} choices",
                 "1".bold(),
-                list.items.len().to_string().bold()
+                choices.len().to_string().bold()
             );
 
             let mut rng = thread_rng();
-
-            let mut choices = list.items.iter().collect::<Vec<_>>();
             choices.shuffle(&mut rng);
 
             let count = rng.gen_range(300..500);
@@ -107,9 +242,11 @@ fn main() -> Result<()> {
             execute!(stdout(), Hide)?;
 
             for _ in 0..count {
-                for i in 0..(choices.len() - 1) {
-                    if rng.gen_bool(0.2) {
-                        choices.swap(i, i + 1);
+                if choices.len() >= 2 {
+                    for i in 0..(choices.len() - 1) {
+                        if rng.gen_bool(0.2) {
+                            choices.swap(i, i + 1);
+                        }
                     }
                 }
 
@@ -126,8 +263,8 @@ fn main() -> Result<()> {
                 column.set_constraint(ColumnConstraint::Absolute(Fixed(36)));
                 column.set_cell_alignment(CellAlignment::Left);
 
-                for (i, item) in choices.iter().take(10).enumerate() {
-                    let mut row = Row::from(vec![(i + 1).to_string(), item.to_string()]);
+                for (i, (name, _)) in choices.iter().take(10).enumerate() {
+                    let mut row = Row::from(vec![(i + 1).to_string(), name.to_string()]);
                     row.max_height(1);
 
                     table.add_row(row);
@@ -138,11 +275,14 @@ fn main() -> Result<()> {
                 }
 
                 for i in 0..choices.len() {
-                    // kill a random element
+                    // kill a random element; a killed marble drops out of the
+                    // pool the winner is drawn from, but this only touches
+                    // the local `choices` copy, not the persisted list
                     // the `i < choices.len()` is not redundant
                     if rng.gen_bool(0.001) && i < choices.len() {
                         execute!(stdout(), Clear(ClearType::CurrentLine))?;
-                        println!("dead: {}", choices.remove(i).as_str().dark_red().bold());
+                        let (name, _) = choices.remove(i);
+                        println!("dead: {}", name.as_str().dark_red().bold());
                     }
                 }
 
@@ -153,8 +293,35 @@ fn main() -> Result<()> {
                 first = false;
             }
 
-            let choice = choices[0];
-            println!("  rolled: {}", choice.as_str().bold().green().reverse());
+            if choices.is_empty() {
+                execute!(stdout(), Show)?;
+                println!("{} ran out of choices", "error:".bold().red());
+                return Ok(());
+            }
+
+            // build a cumulative-weight array and binary-search it so that
+            // heavier marbles are proportionally more likely to win
+            let cumulative = choices
+                .iter()
+                .scan(0u64, |total, (_, weight)| {
+                    *total += *weight as u64;
+                    Some(*total)
+                })
+                .collect::<Vec<_>>();
+
+            let total_weight = *cumulative.last().unwrap();
+
+            let winner_index = if total_weight == 0 {
+                rng.gen_range(0..choices.len())
+            } else {
+                let roll = rng.gen_range(0..total_weight);
+                cumulative.partition_point(|&c| c <= roll)
+            };
+
+            let (name, _) = &choices[winner_index];
+            list.discard(name, "roll")?;
+            list.record_roll(&list_name, name)?;
+            println!("  rolled: {}", name.as_str().bold().green().reverse());
             execute!(stdout(), Show)?;
         }
         Commands::Edit => {
@@ -167,6 +334,71 @@ fn main() -> Result<()> {
             child.wait()?;
             return Ok(());
         }
+        Commands::Browse => {
+            run_browse(&mut list)?;
+        }
+        Commands::Undo => match list.undo()? {
+            Some(entry) => println!(
+                "Restored {} to {} (discarded via {} on {})",
+                entry.name.as_str().underlined(),
+                list_name.bold().green(),
+                entry.kind.bold(),
+                format_timestamp(entry.timestamp)
+            ),
+            None => println!("{} nothing to undo", "error:".bold().red()),
+        },
+        Commands::Trash => {
+            let mut entries = list.trash_entries()?;
+            entries.reverse();
+
+            let mut table = Table::new();
+            table
+                .load_preset(UTF8_FULL)
+                .set_header(vec!["#", "Title", "Kind", "Discarded"]);
+            for (i, entry) in entries.iter().enumerate() {
+                table.add_row(vec![
+                    (i + 1).to_string(),
+                    entry.name.to_string(),
+                    entry.kind.to_string(),
+                    format_timestamp(entry.timestamp),
+                ]);
+            }
+
+            println!("{table}");
+        }
+        Commands::History => {
+            let entries = list
+                .history_entries()?
+                .into_iter()
+                .filter(|entry| entry.list_name == list_name)
+                .collect::<Vec<_>>();
+
+            println!(
+                "{} total rolls for {}",
+                entries.len().to_string().bold(),
+                list_name.bold().green()
+            );
+
+            let mut stats: BTreeMap<String, (u32, u64)> = BTreeMap::new();
+            for entry in &entries {
+                let stat = stats.entry(entry.winner.clone()).or_insert((0, 0));
+                stat.0 += 1;
+                stat.1 = stat.1.max(entry.timestamp);
+            }
+
+            let mut stats = stats.into_iter().collect::<Vec<_>>();
+            stats.sort_by_key(|(_, (count, _))| std::cmp::Reverse(*count));
+
+            let mut table = Table::new();
+            table
+                .load_preset(UTF8_FULL)
+                .set_header(vec!["Marble", "Rolls", "Last Rolled"]);
+            for (name, (count, last_rolled)) in stats {
+                table.add_row(vec![name, count.to_string(), format_timestamp(last_rolled)]);
+            }
+
+            println!("{table}");
+        }
     }
 
     list.save()?;
@@ -174,9 +406,399 @@ fn main() -> Result<()> {
     Ok(())
 }
 
+/// Runs the full-screen browser, mutating `list.items` in place. The caller
+/// is responsible for persisting the list afterwards, same as every other
+/// command.
+fn run_browse(list: &mut ItemList) -> Result<()> {
+    let mut items: Vec<String> = list.items.keys().cloned().collect();
+    let mut cursor = 0usize;
+    let mut top = 0usize;
+    let mut filter = String::new();
+    let mut dirty = true;
+    let mut removed: Vec<String> = Vec::new();
+
+    enable_raw_mode()?;
+    execute!(stdout(), EnterAlternateScreen, Hide)?;
+
+    let result = run_browse_loop(
+        &mut items,
+        &mut cursor,
+        &mut top,
+        &mut filter,
+        &mut dirty,
+        &mut removed,
+    );
+
+    execute!(stdout(), Show, LeaveAlternateScreen)?;
+    disable_raw_mode()?;
+    result?;
+
+    // items added in the browser get the default weight/tags; items that
+    // already existed keep whatever they had
+    let old_items = list.items.clone();
+
+    // items deleted under the cursor go through discard() like any other
+    // removal, so `undo`/`trash` can recover them; this must happen before
+    // `list.items` is rebuilt below, while it still holds their weight/tags
+    for name in removed {
+        list.discard(&name, "remove")?;
+    }
+
+    list.items = items
+        .into_iter()
+        .map(|name| {
+            let item = old_items.get(&name).cloned().unwrap_or_default();
+            (name, item)
+        })
+        .collect();
+
+    Ok(())
+}
+
+fn run_browse_loop(
+    items: &mut Vec<String>,
+    cursor: &mut usize,
+    top: &mut usize,
+    filter: &mut String,
+    dirty: &mut bool,
+    removed: &mut Vec<String>,
+) -> Result<()> {
+    loop {
+        let view = visible_indices(items, filter);
+        if *cursor >= view.len() {
+            *cursor = view.len().saturating_sub(1);
+        }
+
+        let (_, rows) = terminal::size()?;
+        let page_size = rows.saturating_sub(6).max(1) as usize;
+
+        if *cursor < *top {
+            *top = *cursor;
+            *dirty = true;
+        } else if *cursor >= *top + page_size {
+            *top = *cursor + 1 - page_size;
+            *dirty = true;
+        }
+
+        if *dirty {
+            draw_browse(items, &view, *cursor, *top, page_size, filter)?;
+            *dirty = false;
+        }
+
+        let Event::Key(KeyEvent {
+            code,
+            kind: KeyEventKind::Press,
+            ..
+        }) = event::read()?
+        else {
+            continue;
+        };
+
+        match code {
+            KeyCode::Char('q') => return Ok(()),
+            KeyCode::Char('j') | KeyCode::Down if *cursor + 1 < view.len() => {
+                *cursor += 1;
+                *dirty = true;
+            }
+            KeyCode::Char('k') | KeyCode::Up if *cursor > 0 => {
+                *cursor -= 1;
+                *dirty = true;
+            }
+            KeyCode::Char('g') => {
+                *cursor = 0;
+                *dirty = true;
+            }
+            KeyCode::Char('G') => {
+                *cursor = view.len().saturating_sub(1);
+                *dirty = true;
+            }
+            KeyCode::PageDown => {
+                *cursor = (*cursor + page_size).min(view.len().saturating_sub(1));
+                *dirty = true;
+            }
+            KeyCode::PageUp => {
+                *cursor = cursor.saturating_sub(page_size);
+                *dirty = true;
+            }
+            KeyCode::Char('d') if !view.is_empty() => {
+                let name = items.remove(view[*cursor]);
+                removed.push(name);
+                *dirty = true;
+            }
+            KeyCode::Char('a') => {
+                if let Some(name) = prompt_line(rows, "add")?.filter(|name| !name.is_empty()) {
+                    items.push(name);
+                    items.sort();
+                }
+                *dirty = true;
+            }
+            KeyCode::Char('/') => {
+                if let Some(query) = prompt_line(rows, "find")? {
+                    *filter = query;
+                    *cursor = 0;
+                    *top = 0;
+                }
+                *dirty = true;
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Ranks `items` against `filter` using [`fuzzy_match`] and returns the
+/// matching indices best-first; an empty filter keeps every item in its
+/// existing order.
+fn visible_indices(items: &[String], filter: &str) -> Vec<usize> {
+    if filter.is_empty() {
+        return (0..items.len()).collect();
+    }
+
+    let mut scored = items
+        .iter()
+        .enumerate()
+        .filter_map(|(i, name)| fuzzy_match(filter, name).map(|score| (score, i)))
+        .collect::<Vec<_>>();
+    scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+
+    scored.into_iter().map(|(_, i)| i).collect()
+}
+
+/// Redraws the visible window of `view` (indices into `items`) as a table,
+/// highlighting the row under `cursor`. Only called when something actually
+/// changed.
+fn draw_browse(
+    items: &[String],
+    view: &[usize],
+    cursor: usize,
+    top: usize,
+    page_size: usize,
+    filter: &str,
+) -> Result<()> {
+    execute!(stdout(), MoveTo(0, 0), Clear(ClearType::All))?;
+
+    let mut table = Table::new();
+    table.load_preset(UTF8_FULL).set_header(vec!["#", "Title"]);
+
+    let end = (top + page_size).min(view.len());
+    for (i, &index) in view[top..end].iter().enumerate() {
+        let position = top + i;
+        let name = &items[index];
+        let title = if position == cursor {
+            name.as_str().reverse().to_string()
+        } else {
+            name.to_string()
+        };
+
+        table.add_row(vec![(position + 1).to_string(), title]);
+    }
+
+    print!("{}\r\n", table.to_string().replace('\n', "\r\n"));
+    if filter.is_empty() {
+        print!("j/k move  g/G top/bottom  a add  d delete  / filter  q save & quit\r\n");
+    } else {
+        print!("filter: {filter}  (press / to change, empty query clears it)\r\n");
+    }
+
+    stdout().flush()?;
+
+    Ok(())
+}
+
+/// Draws an inline `{label}> ` prompt on the last row and reads a line of
+/// input, returning `None` if the user cancels with Esc.
+fn prompt_line(rows: u16, label: &str) -> Result<Option<String>> {
+    let mut buf = String::new();
+
+    loop {
+        execute!(
+            stdout(),
+            MoveTo(0, rows.saturating_sub(1)),
+            Clear(ClearType::CurrentLine)
+        )?;
+        print!("{label}> {buf}");
+        stdout().flush()?;
+
+        let Event::Key(KeyEvent {
+            code,
+            kind: KeyEventKind::Press,
+            ..
+        }) = event::read()?
+        else {
+            continue;
+        };
+
+        match code {
+            KeyCode::Enter => return Ok(Some(buf)),
+            KeyCode::Esc => return Ok(None),
+            KeyCode::Backspace => {
+                buf.pop();
+            }
+            KeyCode::Char(c) => buf.push(c),
+            _ => {}
+        }
+    }
+}
+
+/// Scores `candidate` against `query` as a case-insensitive subsequence
+/// match: every character of `query` must appear in `candidate` in order,
+/// but not necessarily contiguously. Returns `None` when it doesn't match at
+/// all, otherwise a score that rewards consecutive runs and matches that
+/// land on a word boundary (start of string, after a separator, or a
+/// camelCase hump), so that e.g. "mc" ranks "Moon Cake" above "Mechanic".
+fn fuzzy_match(query: &str, candidate: &str) -> Option<i64> {
+    if query.is_empty() {
+        return Some(0);
+    }
+
+    // `to_lowercase()` can grow the char count (e.g. Turkish `İ` becomes two
+    // chars), so `lower` and `chars` aren't guaranteed to stay the same
+    // length or line up index-for-index; look up `chars` defensively instead
+    // of assuming it mirrors `lower`.
+    let query = query.to_lowercase().chars().collect::<Vec<_>>();
+    let chars = candidate.chars().collect::<Vec<_>>();
+    let lower = candidate.to_lowercase().chars().collect::<Vec<_>>();
+
+    let mut score = 0i64;
+    let mut consecutive = 0i64;
+    let mut qi = 0usize;
+
+    for (i, &ch) in lower.iter().enumerate() {
+        if qi >= query.len() {
+            break;
+        }
+
+        if ch != query[qi] {
+            consecutive = 0;
+            continue;
+        }
+
+        let prev = if i == 0 { None } else { chars.get(i - 1) };
+        let cur = chars.get(i);
+        let at_boundary = i == 0
+            || prev.is_none_or(|p| !p.is_alphanumeric())
+            || prev
+                .zip(cur)
+                .is_some_and(|(p, c)| p.is_lowercase() && c.is_uppercase());
+
+        consecutive += 1;
+        score += 10 + consecutive * 5 + if at_boundary { 15 } else { 0 };
+        qi += 1;
+    }
+
+    (qi == query.len()).then_some(score)
+}
+
+/// A marble's stored state: how likely it is to win and the tags it carries.
+#[derive(Clone)]
+struct Item {
+    weight: u32,
+    tags: BTreeSet<String>,
+}
+
+impl Default for Item {
+    fn default() -> Self {
+        Item {
+            weight: 1,
+            tags: BTreeSet::new(),
+        }
+    }
+}
+
+/// Returns whether `item` should be included under the global `--tag`
+/// filter; no filter matches everything.
+fn matches_tag(item: &Item, tag: &Option<String>) -> bool {
+    match tag {
+        Some(tag) => item.tags.contains(tag),
+        None => true,
+    }
+}
+
+fn tags_to_string(tags: &BTreeSet<String>) -> String {
+    tags.iter().cloned().collect::<Vec<_>>().join(",")
+}
+
+fn parse_tags(raw: &str) -> BTreeSet<String> {
+    if raw.is_empty() {
+        BTreeSet::new()
+    } else {
+        raw.split(',').map(String::from).collect()
+    }
+}
+
+/// A single entry in a list's `.trash` log: an item that was removed or
+/// rolled away, kept around so `undo` can bring it back.
+struct TrashEntry {
+    timestamp: u64,
+    kind: String,
+    weight: u32,
+    tags: BTreeSet<String>,
+    name: String,
+}
+
+impl TrashEntry {
+    /// Parses a trash line, which is either the current `timestamp\tkind\t
+    /// weight\ttags\tname` format or the tagless `timestamp\tkind\tweight\t
+    /// name` format written before tags existed.
+    fn parse(line: String) -> Option<TrashEntry> {
+        match line.splitn(5, '\t').collect::<Vec<_>>().as_slice() {
+            [timestamp, kind, weight, tags, name] => Some(TrashEntry {
+                timestamp: timestamp.parse().ok()?,
+                kind: kind.to_string(),
+                weight: weight.parse().ok()?,
+                tags: parse_tags(tags),
+                name: name.to_string(),
+            }),
+            [timestamp, kind, weight, name] => Some(TrashEntry {
+                timestamp: timestamp.parse().ok()?,
+                kind: kind.to_string(),
+                weight: weight.parse().ok()?,
+                tags: BTreeSet::new(),
+                name: name.to_string(),
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// A single entry in a list's `.history` log: one `Roll` and who won it.
+struct HistoryEntry {
+    timestamp: u64,
+    list_name: String,
+    winner: String,
+}
+
+impl HistoryEntry {
+    fn parse(line: String) -> Option<HistoryEntry> {
+        let mut parts = line.splitn(3, '\t');
+
+        Some(HistoryEntry {
+            timestamp: parts.next()?.parse().ok()?,
+            list_name: parts.next()?.to_string(),
+            winner: parts.next()?.to_string(),
+        })
+    }
+}
+
+fn now_unix() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
+/// Renders a stored unix timestamp for display, e.g. in the `trash` and
+/// `history` tables.
+fn format_timestamp(timestamp: u64) -> String {
+    DateTime::<Utc>::from_timestamp(timestamp as i64, 0)
+        .map(|dt| dt.with_timezone(&Local).format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
 struct ItemList {
     path: PathBuf,
-    items: BTreeSet<String>,
+    trash_path: PathBuf,
+    history_path: PathBuf,
+    items: BTreeMap<String, Item>,
 }
 
 impl ItemList {
@@ -189,17 +811,63 @@ impl ItemList {
 
         data_dir.push(list_name);
 
+        let mut trash_path = data_dir.clone();
+        trash_path.set_extension("trash");
+
+        let mut history_path = data_dir.clone();
+        history_path.set_extension("history");
+
         let items = match File::open(&data_dir) {
-            Ok(file) => BufReader::new(file).lines().flatten().collect(),
-            Err(_) => BTreeSet::new(),
+            Ok(file) => BufReader::new(file)
+                .lines()
+                .map_while(Result::ok)
+                .filter_map(Self::parse_line)
+                .collect(),
+            Err(_) => BTreeMap::new(),
         };
 
         Ok(ItemList {
             path: data_dir,
+            trash_path,
+            history_path,
             items,
         })
     }
 
+    /// Parses a stored line. Three formats are accepted so that lists
+    /// written before weights or tags existed keep loading as-is: a bare
+    /// name (weight 1, no tags), `weight\tname` (no tags), or the current
+    /// `weight\ttags\tname`, where `tags` is a comma-separated list.
+    fn parse_line(line: String) -> Option<(String, Item)> {
+        if line.is_empty() {
+            return None;
+        }
+
+        match line.splitn(3, '\t').collect::<Vec<_>>().as_slice() {
+            [weight, tags, name] => {
+                let weight = weight.parse().unwrap_or(1);
+                Some((
+                    name.to_string(),
+                    Item {
+                        weight,
+                        tags: parse_tags(tags),
+                    },
+                ))
+            }
+            [weight, name] => match weight.parse() {
+                Ok(weight) => Some((
+                    name.to_string(),
+                    Item {
+                        weight,
+                        tags: BTreeSet::new(),
+                    },
+                )),
+                Err(_) => Some((line, Item::default())),
+            },
+            _ => Some((line, Item::default())),
+        }
+    }
+
     fn save(&self) -> Result<()> {
         let file = OpenOptions::new()
             .write(true)
@@ -208,20 +876,167 @@ impl ItemList {
             .open(&self.path)?;
         let mut writer = BufWriter::new(file);
 
-        for line in self.items.iter() {
-            writeln!(writer, "{}", line)?;
+        for (name, item) in self.items.iter() {
+            if item.weight == 1 && item.tags.is_empty() {
+                writeln!(writer, "{}", name)?;
+            } else {
+                writeln!(writer, "{}\t{}\t{}", item.weight, tags_to_string(&item.tags), name)?;
+            }
+        }
+
+        writer.flush()?;
+
+        Ok(())
+    }
+
+    fn add(&mut self, item: String, weight: u32) {
+        self.items.insert(
+            item,
+            Item {
+                weight,
+                tags: BTreeSet::new(),
+            },
+        );
+    }
+
+    /// Removes `item` from the list and appends it to the trash log, tagged
+    /// with `kind` (e.g. `"remove"` or `"roll"`) so `undo` knows what
+    /// happened to it. Returns whether the item was present.
+    fn discard(&mut self, item: &str, kind: &str) -> Result<bool> {
+        let Some(stored) = self.items.remove(item) else {
+            return Ok(false);
+        };
+
+        let file = OpenOptions::new()
+            .append(true)
+            .create(true)
+            .open(&self.trash_path)?;
+        let mut writer = BufWriter::new(file);
+        writeln!(
+            writer,
+            "{}\t{}\t{}\t{}\t{}",
+            now_unix(),
+            kind,
+            stored.weight,
+            tags_to_string(&stored.tags),
+            item
+        )?;
+        writer.flush()?;
+
+        Ok(true)
+    }
+
+    /// Reads the trash log, oldest first.
+    fn trash_entries(&self) -> Result<Vec<TrashEntry>> {
+        match File::open(&self.trash_path) {
+            Ok(file) => Ok(BufReader::new(file)
+                .lines()
+                .map_while(Result::ok)
+                .filter_map(TrashEntry::parse)
+                .collect()),
+            Err(_) => Ok(Vec::new()),
         }
+    }
+
+    /// Restores the most recently discarded item, removing it from the
+    /// trash log.
+    fn undo(&mut self) -> Result<Option<TrashEntry>> {
+        let mut entries = self.trash_entries()?;
+        let Some(entry) = entries.pop() else {
+            return Ok(None);
+        };
 
+        let file = OpenOptions::new()
+            .write(true)
+            .truncate(true)
+            .create(true)
+            .open(&self.trash_path)?;
+        let mut writer = BufWriter::new(file);
+        for remaining in &entries {
+            writeln!(
+                writer,
+                "{}\t{}\t{}\t{}\t{}",
+                remaining.timestamp,
+                remaining.kind,
+                remaining.weight,
+                tags_to_string(&remaining.tags),
+                remaining.name
+            )?;
+        }
+        writer.flush()?;
+
+        self.items.insert(
+            entry.name.clone(),
+            Item {
+                weight: entry.weight,
+                tags: entry.tags.clone(),
+            },
+        );
+
+        Ok(Some(entry))
+    }
+
+    /// Appends a `Roll` outcome to the history log.
+    fn record_roll(&self, list_name: &str, winner: &str) -> Result<()> {
+        let file = OpenOptions::new()
+            .append(true)
+            .create(true)
+            .open(&self.history_path)?;
+        let mut writer = BufWriter::new(file);
+        writeln!(writer, "{}\t{}\t{}", now_unix(), list_name, winner)?;
         writer.flush()?;
 
         Ok(())
     }
 
-    fn add(&mut self, item: String) {
-        self.items.insert(item);
+    /// Reads the roll history, oldest first.
+    fn history_entries(&self) -> Result<Vec<HistoryEntry>> {
+        match File::open(&self.history_path) {
+            Ok(file) => Ok(BufReader::new(file)
+                .lines()
+                .map_while(Result::ok)
+                .filter_map(HistoryEntry::parse)
+                .collect()),
+            Err(_) => Ok(Vec::new()),
+        }
+    }
+
+    fn set_weight(&mut self, item: &str, value: u32) -> bool {
+        match self.items.get_mut(item) {
+            Some(stored) => {
+                stored.weight = value;
+                true
+            }
+            None => false,
+        }
     }
 
-    fn remove(&mut self, item: &String) -> bool {
-        self.items.remove(item)
+    /// Adds `tags` to `item`. Returns whether the item was present.
+    fn add_tags(&mut self, item: &str, tags: &[String]) -> bool {
+        match self.items.get_mut(item) {
+            Some(stored) => {
+                stored.tags.extend(tags.iter().cloned());
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Removes `tags` from `item`, or clears all tags if `tags` is empty.
+    /// Returns whether the item was present.
+    fn remove_tags(&mut self, item: &str, tags: &[String]) -> bool {
+        match self.items.get_mut(item) {
+            Some(stored) => {
+                if tags.is_empty() {
+                    stored.tags.clear();
+                } else {
+                    for tag in tags {
+                        stored.tags.remove(tag);
+                    }
+                }
+                true
+            }
+            None => false,
+        }
     }
 }